@@ -1,4 +1,4 @@
-use image::{DynamicImage, ImageBuffer, Luma, Pixel, Primitive, Rgb};
+use image::{Bgr, Bgra, DynamicImage, ImageBuffer, Luma, LumaA, Pixel, Primitive, Rgb, Rgba};
 use printpdf::*;
 use std::fs::File;
 use std::io::BufWriter;
@@ -16,10 +16,166 @@ struct Args {
 
     #[structopt(default_value = "100", short = "a", long)]
     alpha: u8,
+
+    /// Combine all input files into a single multi-page PDF written to OUT.pdf,
+    /// instead of producing one PDF per input file.
+    #[structopt(short = "c", long, parse(from_os_str))]
+    combine: Option<PathBuf>,
+
+    /// Color used to flatten transparent pixels against, as "R,G,B" (0-255 each).
+    #[structopt(long, default_value = "255,255,255")]
+    background: Background,
+
+    /// Fit each page onto a standard paper size (A4, Letter, or Legal) instead of sizing
+    /// the page to the image's own pixel dimensions.
+    #[structopt(long)]
+    page_size: Option<PaperSize>,
+
+    /// Margin, in millimeters, to leave around the fitted image when `--page-size` is set.
+    #[structopt(long, default_value = "0")]
+    margin: f64,
+
+    /// Page rotation to use when `--page-size` is set.
+    #[structopt(long, default_value = "portrait")]
+    orientation: Orientation,
+
+    /// Downsample the embedded image if it would exceed this many dots per inch at its
+    /// printed size, to control output file size.
+    #[structopt(long)]
+    max_dpi: Option<f64>,
+
+    /// Resampling filter used when downsampling for `--max-dpi`.
+    #[structopt(long, default_value = "lanczos3")]
+    filter: ResizeFilter,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ResizeFilter {
+    Nearest,
+    Triangle,
+    CatmullRom,
+    Gaussian,
+    Lanczos3,
+}
+
+impl std::str::FromStr for ResizeFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "nearest" => Ok(ResizeFilter::Nearest),
+            "triangle" => Ok(ResizeFilter::Triangle),
+            "catmullrom" | "catmull-rom" => Ok(ResizeFilter::CatmullRom),
+            "gaussian" => Ok(ResizeFilter::Gaussian),
+            "lanczos3" => Ok(ResizeFilter::Lanczos3),
+            _ => Err(format!(
+                "unknown filter `{}` (expected nearest, triangle, catmullrom, gaussian, or lanczos3)",
+                s
+            )),
+        }
+    }
+}
+
+impl From<ResizeFilter> for image::imageops::FilterType {
+    fn from(filter: ResizeFilter) -> Self {
+        match filter {
+            ResizeFilter::Nearest => image::imageops::FilterType::Nearest,
+            ResizeFilter::Triangle => image::imageops::FilterType::Triangle,
+            ResizeFilter::CatmullRom => image::imageops::FilterType::CatmullRom,
+            ResizeFilter::Gaussian => image::imageops::FilterType::Gaussian,
+            ResizeFilter::Lanczos3 => image::imageops::FilterType::Lanczos3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum PaperSize {
+    A4,
+    Letter,
+    Legal,
+}
+
+impl PaperSize {
+    /// Page dimensions in points, portrait orientation, as `(width, height)`.
+    fn dimensions_pt(&self) -> (f64, f64) {
+        let (width_mm, height_mm) = match self {
+            PaperSize::A4 => (210.0, 297.0),
+            PaperSize::Letter => (215.9, 279.4),
+            PaperSize::Legal => (215.9, 355.6),
+        };
+        (mm_to_pt(width_mm), mm_to_pt(height_mm))
+    }
+}
+
+impl std::str::FromStr for PaperSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "a4" => Ok(PaperSize::A4),
+            "letter" => Ok(PaperSize::Letter),
+            "legal" => Ok(PaperSize::Legal),
+            _ => Err(format!("unknown page size `{}` (expected A4, Letter, or Legal)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Orientation {
+    Portrait,
+    Landscape,
+}
+
+impl std::str::FromStr for Orientation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "portrait" => Ok(Orientation::Portrait),
+            "landscape" => Ok(Orientation::Landscape),
+            _ => Err(format!("unknown orientation `{}` (expected portrait or landscape)", s)),
+        }
+    }
 }
 
 const DPI: f64 = 300.0;
 
+/// An opaque RGB color that transparent pixels are composited against when an input
+/// image carries a true per-pixel alpha channel (e.g. a transparent PNG).
+#[derive(Debug, Clone, Copy)]
+struct Background {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+impl Background {
+    /// Approximate luminance of this color, used when flattening single-channel
+    /// (Luma) images that carry an alpha channel.
+    fn luma(&self) -> f32 {
+        0.299 * f32::from(self.r) + 0.587 * f32::from(self.g) + 0.114 * f32::from(self.b)
+    }
+}
+
+impl std::str::FromStr for Background {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(',').collect();
+        match parts.as_slice() {
+            [r, g, b] => {
+                let parse = |p: &str| p.trim().parse::<u8>().map_err(|e| e.to_string());
+                Ok(Background {
+                    r: parse(r)?,
+                    g: parse(g)?,
+                    b: parse(b)?,
+                })
+            }
+            _ => Err(format!("expected \"R,G,B\" but got `{}`", s)),
+        }
+    }
+}
+
 fn open_image(path: &Path) -> image::ImageResult<image::DynamicImage> {
     image::io::Reader::open(path)?.decode()
 }
@@ -79,7 +235,83 @@ impl<S: Primitive + std::fmt::Debug + 'static> MulAlpha for Rgb<S> {
     }
 }
 
-fn mul_alpha_to_image(img: &DynamicImage, alpha: f32) -> DynamicImage {
+impl<S: Primitive + std::fmt::Debug + 'static> MulAlpha for Bgr<S> {
+    fn mul_alpha(&self, alpha: f32) -> Self {
+        self.map_with_alpha(
+            |p| {
+                let max_pixel: f32 = num_traits::NumCast::from(S::max_value()).unwrap();
+                let bgrnd: f32 = (1.0 - alpha) * max_pixel;
+                let p_as_f32: f32 = num_traits::NumCast::from(p).unwrap();
+                let fgrnd: f32 = alpha * p_as_f32;
+                num_traits::NumCast::from(bgrnd + fgrnd).unwrap()
+            },
+            |_| S::max_value(),
+        )
+    }
+}
+
+/// Composites a translucent subpixel `fg` (with normalized alpha `a`) over an opaque
+/// `bg` subpixel: `out = bg*(1-a) + fg*a`.
+fn composite(bg: f32, fg: f32, a: f32) -> f32 {
+    bg * (1.0 - a) + fg * a
+}
+
+fn flatten_luma_a8(buffer: &ImageBuffer<LumaA<u8>, Vec<u8>>, background: Background) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+    let bg = background.luma();
+    ImageBuffer::from_fn(buffer.width(), buffer.height(), |x, y| {
+        let px = buffer.get_pixel(x, y);
+        let a = f32::from(px[1]) / 255.0;
+        Luma([composite(bg, f32::from(px[0]), a).round() as u8])
+    })
+}
+
+fn flatten_luma_a16(buffer: &ImageBuffer<LumaA<u16>, Vec<u16>>, background: Background) -> ImageBuffer<Luma<u16>, Vec<u16>> {
+    let bg = background.luma() / 255.0 * 65535.0;
+    ImageBuffer::from_fn(buffer.width(), buffer.height(), |x, y| {
+        let px = buffer.get_pixel(x, y);
+        let a = f32::from(px[1]) / 65535.0;
+        Luma([composite(bg, f32::from(px[0]), a).round() as u16])
+    })
+}
+
+fn flatten_rgba8(buffer: &ImageBuffer<Rgba<u8>, Vec<u8>>, background: Background) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    ImageBuffer::from_fn(buffer.width(), buffer.height(), |x, y| {
+        let px = buffer.get_pixel(x, y);
+        let a = f32::from(px[3]) / 255.0;
+        Rgb([
+            composite(f32::from(background.r), f32::from(px[0]), a).round() as u8,
+            composite(f32::from(background.g), f32::from(px[1]), a).round() as u8,
+            composite(f32::from(background.b), f32::from(px[2]), a).round() as u8,
+        ])
+    })
+}
+
+fn flatten_rgba16(buffer: &ImageBuffer<Rgba<u16>, Vec<u16>>, background: Background) -> ImageBuffer<Rgb<u16>, Vec<u16>> {
+    let scale = 65535.0 / 255.0;
+    ImageBuffer::from_fn(buffer.width(), buffer.height(), |x, y| {
+        let px = buffer.get_pixel(x, y);
+        let a = f32::from(px[3]) / 65535.0;
+        Rgb([
+            composite(f32::from(background.r) * scale, f32::from(px[0]), a).round() as u16,
+            composite(f32::from(background.g) * scale, f32::from(px[1]), a).round() as u16,
+            composite(f32::from(background.b) * scale, f32::from(px[2]), a).round() as u16,
+        ])
+    })
+}
+
+fn flatten_bgra8(buffer: &ImageBuffer<Bgra<u8>, Vec<u8>>, background: Background) -> ImageBuffer<Bgr<u8>, Vec<u8>> {
+    ImageBuffer::from_fn(buffer.width(), buffer.height(), |x, y| {
+        let px = buffer.get_pixel(x, y);
+        let a = f32::from(px[3]) / 255.0;
+        Bgr([
+            composite(f32::from(background.b), f32::from(px[0]), a).round() as u8,
+            composite(f32::from(background.g), f32::from(px[1]), a).round() as u8,
+            composite(f32::from(background.r), f32::from(px[2]), a).round() as u8,
+        ])
+    })
+}
+
+fn mul_alpha_to_image(img: &DynamicImage, alpha: f32, background: Background) -> DynamicImage {
     match img {
         DynamicImage::ImageLuma8(buffer) => {
             DynamicImage::ImageLuma8(MulAlpha::mul_alpha_buffer(buffer, alpha))
@@ -90,49 +322,529 @@ fn mul_alpha_to_image(img: &DynamicImage, alpha: f32) -> DynamicImage {
         DynamicImage::ImageRgb16(buffer) => {
             DynamicImage::ImageRgb16(MulAlpha::mul_alpha_buffer(buffer, alpha))
         }
+        DynamicImage::ImageLuma16(buffer) => {
+            DynamicImage::ImageLuma16(MulAlpha::mul_alpha_buffer(buffer, alpha))
+        }
+        DynamicImage::ImageBgr8(buffer) => {
+            DynamicImage::ImageBgr8(MulAlpha::mul_alpha_buffer(buffer, alpha))
+        }
+        DynamicImage::ImageLumaA8(buffer) => {
+            let flattened = flatten_luma_a8(buffer, background);
+            DynamicImage::ImageLuma8(MulAlpha::mul_alpha_buffer(&flattened, alpha))
+        }
+        DynamicImage::ImageLumaA16(buffer) => {
+            let flattened = flatten_luma_a16(buffer, background);
+            DynamicImage::ImageLuma16(MulAlpha::mul_alpha_buffer(&flattened, alpha))
+        }
+        DynamicImage::ImageRgba8(buffer) => {
+            let flattened = flatten_rgba8(buffer, background);
+            DynamicImage::ImageRgb8(MulAlpha::mul_alpha_buffer(&flattened, alpha))
+        }
+        DynamicImage::ImageRgba16(buffer) => {
+            let flattened = flatten_rgba16(buffer, background);
+            DynamicImage::ImageRgb16(MulAlpha::mul_alpha_buffer(&flattened, alpha))
+        }
+        DynamicImage::ImageBgra8(buffer) => {
+            let flattened = flatten_bgra8(buffer, background);
+            DynamicImage::ImageBgr8(MulAlpha::mul_alpha_buffer(&flattened, alpha))
+        }
         _ => unimplemented!("add_alpha_to_image"),
     }
 }
 
-fn process_image(args: &Args, img: image::DynamicImage) -> image::DynamicImage {
-    let mut output = img;
+/// The physical size, in inches, that a `width_px` by `height_px` image will occupy once
+/// placed on its page: the fitted size on `--page-size` paper, or its native size at
+/// `DPI` otherwise.
+fn target_physical_size_in(args: &Args, width_px: u32, height_px: u32) -> std::result::Result<(f64, f64), ()> {
+    let natural_width_pt = f64::from(width_px) / DPI * PT_PER_INCH;
+    let natural_height_pt = f64::from(height_px) / DPI * PT_PER_INCH;
+    let (_, _, scale) = fit_to_page(args, natural_width_pt, natural_height_pt)?;
+
+    Ok((
+        f64::from(width_px) / DPI * scale,
+        f64::from(height_px) / DPI * scale,
+    ))
+}
+
+/// Downsamples `img` so it embeds at no more than `--max-dpi` at its printed size. A
+/// no-op when `--max-dpi` isn't set or the source is already at or below the target.
+/// Returns the image alongside the DPI it now actually embeds at, since that may be
+/// `--max-dpi` rather than `DPI` — callers that size a page from pixel count (i.e. when
+/// `--page-size` isn't set) must divide by this, not by `DPI`, or the resize also
+/// shrinks the physical page.
+fn downsample_to_max_dpi(args: &Args, img: DynamicImage) -> std::result::Result<(DynamicImage, f64), ()> {
+    let max_dpi = match args.max_dpi {
+        Some(max_dpi) => max_dpi,
+        None => return Ok((img, DPI)),
+    };
+
+    let (width_px, height_px) = (img.width(), img.height());
+    let (target_width_in, target_height_in) = target_physical_size_in(args, width_px, height_px)?;
+    let current_dpi =
+        (f64::from(width_px) / target_width_in).max(f64::from(height_px) / target_height_in);
+
+    if current_dpi <= max_dpi {
+        return Ok((img, DPI));
+    }
+
+    let new_width = (target_width_in * max_dpi).round().max(1.0) as u32;
+    let new_height = (target_height_in * max_dpi).round().max(1.0) as u32;
+
+    Ok((img.resize_exact(new_width, new_height, args.filter.into()), max_dpi))
+}
+
+fn process_image(args: &Args, img: image::DynamicImage) -> std::result::Result<(image::DynamicImage, f64), ()> {
+    // Downsample first so the alpha-flatten pass below (the most expensive, per-pixel
+    // step) runs over the smaller buffer, not the original scan.
+    let (mut output, dpi) = downsample_to_max_dpi(args, img)?;
+
+    // Flatten any real alpha channel against `--background` *before* converting to gray:
+    // `grayscale()` below drops alpha straight to `Luma8`, so doing this after would
+    // discard transparency information instead of compositing it.
+    if output.color().has_alpha() || args.alpha < 100 {
+        let alpha = f32::from(args.alpha) / 100.0;
+        let temp = mul_alpha_to_image(&output, alpha, args.background);
+        output = temp;
+    }
 
     if args.to_gray {
         let temp = image::DynamicImage::ImageLuma8(image::imageops::grayscale(&output));
         output = temp;
     }
 
-    if args.alpha < 100 {
-        let alpha = f32::from(args.alpha) / 100.0;
-        let temp = mul_alpha_to_image(&output, alpha);
-        output = temp;
+    Ok((output, dpi))
+}
+
+/// A decoded input, ready to be laid onto a PDF page: either a raster image embedded
+/// as a bitmap XObject, or a vector image (from SVG) drawn as native paths so it stays
+/// crisp at any zoom level.
+enum Page {
+    /// `dpi` is the resolution this image is actually embedded at — `DPI` normally, or
+    /// `--max-dpi` when `downsample_to_max_dpi` had to shrink it — so the page this
+    /// lands on (when `--page-size` isn't set) is sized to the image's *intended*
+    /// physical dimensions rather than its post-resize pixel count divided by `DPI`.
+    Raster {
+        image: image::DynamicImage,
+        dpi: f64,
+    },
+    Vector(VectorPage),
+}
+
+/// One SVG's worth of flattened paths, in PDF points with the origin at the page's
+/// top-left (flipped to PDF's bottom-left origin at render time).
+struct VectorPage {
+    width_pt: f64,
+    height_pt: f64,
+    paths: Vec<VectorPath>,
+}
+
+/// A single filled/stroked subpath, already flattened to line segments.
+struct VectorPath {
+    points: Vec<(f64, f64)>,
+    closed: bool,
+    fill: Option<(u8, u8, u8)>,
+    stroke: Option<((u8, u8, u8), f64)>,
+}
+
+fn is_svg(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case("svg"))
+        .unwrap_or(false)
+}
+
+/// SVG user units are defined at 96 per inch; PDF points are 72 per inch.
+const SVG_UNITS_PER_INCH: f64 = 96.0;
+const PT_PER_INCH: f64 = 72.0;
+const MM_PER_INCH: f64 = 25.4;
+
+fn mm_to_pt(mm: f64) -> f64 {
+    mm * PT_PER_INCH / MM_PER_INCH
+}
+
+fn open_svg(path: &Path) -> std::result::Result<VectorPage, ()> {
+    let svg_data = std::fs::read(path).map_err(|_| ())?;
+    let tree = usvg::Tree::from_data(&svg_data, &usvg::Options::default()).map_err(|_| ())?;
+
+    let width_pt = f64::from(tree.size.width()) * PT_PER_INCH / SVG_UNITS_PER_INCH;
+    let height_pt = f64::from(tree.size.height()) * PT_PER_INCH / SVG_UNITS_PER_INCH;
+
+    let mut paths = Vec::new();
+    collect_vector_paths(&tree.root, &mut paths);
+
+    Ok(VectorPage {
+        width_pt,
+        height_pt,
+        paths,
+    })
+}
+
+fn collect_vector_paths(node: &usvg::Node, paths: &mut Vec<VectorPath>) {
+    for child in node.children() {
+        if let usvg::NodeKind::Path(ref svg_path) = *child.borrow() {
+            paths.extend(path_to_vector_paths(svg_path, &child.abs_transform()));
+        }
+        collect_vector_paths(&child, paths);
     }
+}
+
+fn path_to_vector_paths(svg_path: &usvg::Path, transform: &usvg::Transform) -> Vec<VectorPath> {
+    let to_pt = |x: f64, y: f64| -> (f64, f64) {
+        let (x, y) = transform.apply(x, y);
+        (x * PT_PER_INCH / SVG_UNITS_PER_INCH, y * PT_PER_INCH / SVG_UNITS_PER_INCH)
+    };
+
+    let fill = svg_path.fill.as_ref().and_then(|fill| paint_to_rgb(&fill.paint));
+    let stroke = svg_path.stroke.as_ref().and_then(|stroke| {
+        let color = paint_to_rgb(&stroke.paint)?;
+        let width = stroke.width.get() * PT_PER_INCH / SVG_UNITS_PER_INCH;
+        Some((color, width))
+    });
+
+    let mut subpaths = Vec::new();
+    let mut current = Vec::new();
+    let mut closed = false;
+    let mut cursor = (0.0, 0.0);
 
-    output
+    for segment in svg_path.data.segments() {
+        match segment {
+            usvg::PathSegment::MoveTo { x, y } => {
+                flush_subpath(&mut subpaths, &mut current, closed, fill, stroke);
+                closed = false;
+                cursor = to_pt(x, y);
+                current.push(cursor);
+            }
+            usvg::PathSegment::LineTo { x, y } => {
+                cursor = to_pt(x, y);
+                current.push(cursor);
+            }
+            usvg::PathSegment::CurveTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+            } => {
+                let p1 = to_pt(x1, y1);
+                let p2 = to_pt(x2, y2);
+                let p3 = to_pt(x, y);
+                flatten_cubic(cursor, p1, p2, p3, &mut current);
+                cursor = p3;
+            }
+            usvg::PathSegment::ClosePath => {
+                closed = true;
+            }
+        }
+    }
+    flush_subpath(&mut subpaths, &mut current, closed, fill, stroke);
+
+    subpaths
 }
 
-fn create_pdf(doc_name: &str, img_view: &image::DynamicImage) -> PdfDocumentReference {
-    let pdf_image = Image::from_dynamic_image(img_view);
-    let (doc, page, layer) = PdfDocument::new(
+fn flush_subpath(
+    subpaths: &mut Vec<VectorPath>,
+    current: &mut Vec<(f64, f64)>,
+    closed: bool,
+    fill: Option<(u8, u8, u8)>,
+    stroke: Option<((u8, u8, u8), f64)>,
+) {
+    if current.len() > 1 {
+        subpaths.push(VectorPath {
+            points: std::mem::take(current),
+            closed,
+            fill,
+            stroke,
+        });
+    } else {
+        current.clear();
+    }
+}
+
+/// Flattens a cubic Bezier into line segments so it can be expressed with printpdf's
+/// straight-line `Line` primitive.
+fn flatten_cubic(p0: (f64, f64), p1: (f64, f64), p2: (f64, f64), p3: (f64, f64), out: &mut Vec<(f64, f64)>) {
+    const STEPS: usize = 16;
+    for i in 1..=STEPS {
+        let t = i as f64 / STEPS as f64;
+        let mt = 1.0 - t;
+        let x = mt.powi(3) * p0.0 + 3.0 * mt.powi(2) * t * p1.0 + 3.0 * mt * t.powi(2) * p2.0 + t.powi(3) * p3.0;
+        let y = mt.powi(3) * p0.1 + 3.0 * mt.powi(2) * t * p1.1 + 3.0 * mt * t.powi(2) * p2.1 + t.powi(3) * p3.1;
+        out.push((x, y));
+    }
+}
+
+fn paint_to_rgb(paint: &usvg::Paint) -> Option<(u8, u8, u8)> {
+    match paint {
+        usvg::Paint::Color(color) => Some((color.red, color.green, color.blue)),
+        // We only emit flat fills/strokes, so approximate gradients with the average
+        // of their stop colors rather than dropping the fill/stroke entirely.
+        usvg::Paint::LinearGradient(gradient) => Some(average_stop_color(&gradient.base.stops)),
+        usvg::Paint::RadialGradient(gradient) => Some(average_stop_color(&gradient.base.stops)),
+        // Patterns are tiles, not a color ramp, so there's no principled single color
+        // to derive; fall back to a mid gray instead of rendering nothing.
+        usvg::Paint::Pattern(_) => Some((128, 128, 128)),
+    }
+}
+
+/// Averages a gradient's stop colors into one flat RGB color.
+fn average_stop_color(stops: &[usvg::Stop]) -> (u8, u8, u8) {
+    let (mut r, mut g, mut b) = (0u32, 0u32, 0u32);
+    for stop in stops {
+        r += u32::from(stop.color.red);
+        g += u32::from(stop.color.green);
+        b += u32::from(stop.color.blue);
+    }
+    let count = stops.len().max(1) as u32;
+    ((r / count) as u8, (g / count) as u8, (b / count) as u8)
+}
+
+fn desaturate_rgb((r, g, b): (u8, u8, u8)) -> (u8, u8, u8) {
+    let luma = (0.299 * f64::from(r) + 0.587 * f64::from(g) + 0.114 * f64::from(b)).round() as u8;
+    (luma, luma, luma)
+}
+
+fn desaturate_vector_page(page: &mut VectorPage) {
+    for path in &mut page.paths {
+        path.fill = path.fill.map(desaturate_rgb);
+        path.stroke = path.stroke.map(|(color, width)| (desaturate_rgb(color), width));
+    }
+}
+
+fn load_page(args: &Args, file: &Path) -> std::result::Result<Page, ()> {
+    if is_svg(file) {
+        let mut page = open_svg(file)?;
+        if args.to_gray {
+            desaturate_vector_page(&mut page);
+        }
+        Ok(Page::Vector(page))
+    } else {
+        let image = open_image(file).map_err(|_| ())?;
+        let (image, dpi) = process_image(args, image)?;
+        Ok(Page::Raster { image, dpi })
+    }
+}
+
+fn natural_dimensions_pt(page: &Page) -> (Pt, Pt) {
+    match page {
+        Page::Raster { image, dpi } => {
+            let pdf_image = Image::from_dynamic_image(image);
+            (
+                pdf_image.image.width.into_pt(*dpi),
+                pdf_image.image.height.into_pt(*dpi),
+            )
+        }
+        Page::Vector(svg) => (Pt(svg.width_pt), Pt(svg.height_pt)),
+    }
+}
+
+/// Where and at what scale a page's content is drawn on the final PDF page. When
+/// `--page-size` isn't set, the page is sized to the content's own dimensions and drawn
+/// at 1:1 (`fitted` is `false`). Otherwise the content is scaled uniformly to fit inside
+/// the paper size, minus `--margin` on each side, and centered (`fitted` is `true`).
+struct PageLayout {
+    width_pt: f64,
+    height_pt: f64,
+    scale: f64,
+    translate_x_pt: f64,
+    translate_y_pt: f64,
+    fitted: bool,
+}
+
+/// Computes the final page size (pt) and the uniform scale factor that fits a
+/// `natural_width_pt` by `natural_height_pt` box onto the paper chosen by `--page-size`
+/// (honoring `--margin` and `--orientation`), or leaves it at its natural size (scale
+/// `1.0`) when `--page-size` isn't set. Errors if `--margin` is large enough to leave no
+/// positive printable area, rather than silently producing a negative (mirrored) scale.
+fn fit_to_page(args: &Args, natural_width_pt: f64, natural_height_pt: f64) -> std::result::Result<(f64, f64, f64), ()> {
+    match args.page_size {
+        None => Ok((natural_width_pt, natural_height_pt, 1.0)),
+        Some(paper_size) => {
+            let (mut page_width_pt, mut page_height_pt) = paper_size.dimensions_pt();
+            if let Orientation::Landscape = args.orientation {
+                std::mem::swap(&mut page_width_pt, &mut page_height_pt);
+            }
+
+            let margin_pt = mm_to_pt(args.margin);
+            let printable_width_pt = page_width_pt - 2.0 * margin_pt;
+            let printable_height_pt = page_height_pt - 2.0 * margin_pt;
+            if printable_width_pt <= 0.0 || printable_height_pt <= 0.0 {
+                return Err(());
+            }
+
+            let scale = (printable_width_pt / natural_width_pt).min(printable_height_pt / natural_height_pt);
+
+            Ok((page_width_pt, page_height_pt, scale))
+        }
+    }
+}
+
+fn compute_layout(args: &Args, natural: (Pt, Pt)) -> std::result::Result<PageLayout, ()> {
+    let (natural_width, natural_height) = (natural.0 .0, natural.1 .0);
+    let (page_width, page_height, scale) = fit_to_page(args, natural_width, natural_height)?;
+    let fitted = args.page_size.is_some();
+
+    let (translate_x_pt, translate_y_pt) = if fitted {
+        (
+            (page_width - natural_width * scale) / 2.0,
+            (page_height - natural_height * scale) / 2.0,
+        )
+    } else {
+        (0.0, 0.0)
+    };
+
+    Ok(PageLayout {
+        width_pt: page_width,
+        height_pt: page_height,
+        scale,
+        translate_x_pt,
+        translate_y_pt,
+        fitted,
+    })
+}
+
+fn render_page_content(layer: PdfLayerReference, page: &Page, layout: &PageLayout) {
+    match page {
+        Page::Raster { image, dpi } => {
+            let pdf_image = Image::from_dynamic_image(image);
+            if layout.fitted {
+                pdf_image.add_to_layer(
+                    layer,
+                    Some(Mm::from(Pt(layout.translate_x_pt))),
+                    Some(Mm::from(Pt(layout.translate_y_pt))),
+                    None,
+                    Some(layout.scale),
+                    Some(layout.scale),
+                    Some(*dpi),
+                );
+            } else {
+                pdf_image.add_to_layer(layer, None, None, None, None, None, Some(*dpi));
+            }
+        }
+        Page::Vector(svg) => {
+            for path in &svg.paths {
+                render_vector_path(&layer, path, svg.height_pt, layout);
+            }
+        }
+    }
+}
+
+fn render_vector_path(layer: &PdfLayerReference, path: &VectorPath, content_height_pt: f64, layout: &PageLayout) {
+    let scaled_height_pt = content_height_pt * layout.scale;
+
+    let points: Vec<(Point, bool)> = path
+        .points
+        .iter()
+        .map(|&(x, y)| {
+            let x = x * layout.scale + layout.translate_x_pt;
+            let y = (scaled_height_pt - y * layout.scale) + layout.translate_y_pt;
+            (Point::new(Mm::from(Pt(x)), Mm::from(Pt(y))), false)
+        })
+        .collect();
+
+    let line = Line {
+        points,
+        is_closed: path.closed,
+        has_fill: path.fill.is_some(),
+        has_stroke: path.stroke.is_some(),
+        is_clipping_path: false,
+    };
+
+    if let Some((r, g, b)) = path.fill {
+        layer.set_fill_color(rgb_to_pdf_color(r, g, b));
+    }
+    if let Some(((r, g, b), width)) = path.stroke {
+        layer.set_outline_color(rgb_to_pdf_color(r, g, b));
+        layer.set_outline_thickness(width * layout.scale);
+    }
+
+    layer.add_shape(line);
+}
+
+fn rgb_to_pdf_color(r: u8, g: u8, b: u8) -> Color {
+    Color::Rgb(printpdf::Rgb::new(
+        f64::from(r) / 255.0,
+        f64::from(g) / 255.0,
+        f64::from(b) / 255.0,
+        None,
+    ))
+}
+
+fn create_pdf(args: &Args, doc_name: &str, page: &Page) -> std::result::Result<PdfDocumentReference, ()> {
+    let layout = compute_layout(args, natural_dimensions_pt(page))?;
+    let (doc, pdf_page, layer) = PdfDocument::new(
+        doc_name,
+        Mm::from(Pt(layout.width_pt)),
+        Mm::from(Pt(layout.height_pt)),
+        "Layer 1",
+    );
+
+    let current_layer = doc.get_page(pdf_page).get_layer(layer);
+    render_page_content(current_layer, page, &layout);
+
+    Ok(doc)
+}
+
+/// Builds a single multi-page `PdfDocument` from a sequence of `(bookmark_name, page)` pairs.
+/// The first page determines the document's initial size; every subsequent page gets its own
+/// size. Each page is bookmarked with its source name so the resulting PDF has a navigable
+/// outline.
+fn create_combined_pdf(args: &Args, doc_name: &str, pages: &[(String, Page)]) -> std::result::Result<PdfDocumentReference, ()> {
+    let mut pages = pages.iter();
+    let (first_name, first_page) = pages.next().expect("combine requires at least one image");
+
+    let first_layout = compute_layout(args, natural_dimensions_pt(first_page))?;
+    let (doc, pdf_page, layer) = PdfDocument::new(
         doc_name,
-        pdf_image.image.width.into_pt(DPI).into(),
-        pdf_image.image.height.into_pt(DPI).into(),
+        Mm::from(Pt(first_layout.width_pt)),
+        Mm::from(Pt(first_layout.height_pt)),
         "Layer 1",
     );
 
-    let current_layer = doc.get_page(page).get_layer(layer);
-    pdf_image.add_to_layer(current_layer, None, None, None, None, None, Some(DPI));
+    let current_layer = doc.get_page(pdf_page).get_layer(layer);
+    render_page_content(current_layer, first_page, &first_layout);
+    doc.add_bookmark(first_name, pdf_page);
+
+    for (name, page) in pages {
+        let layout = compute_layout(args, natural_dimensions_pt(page))?;
+        let (pdf_page, layer) = doc.add_page(
+            Mm::from(Pt(layout.width_pt)),
+            Mm::from(Pt(layout.height_pt)),
+            "Layer 1",
+        );
 
-    doc
+        let current_layer = doc.get_page(pdf_page).get_layer(layer);
+        render_page_content(current_layer, page, &layout);
+        doc.add_bookmark(name, pdf_page);
+    }
+
+    Ok(doc)
 }
 
 fn main() -> std::result::Result<(), ()> {
     let args = Args::from_args();
 
+    if let Some(outfile) = &args.combine {
+        if args.files.is_empty() {
+            return Err(());
+        }
+
+        let mut pages = Vec::with_capacity(args.files.len());
+        for file in &args.files {
+            let page = load_page(&args, file)?;
+            pages.push((file.to_string_lossy().into_owned(), page));
+        }
+
+        let pdf = create_combined_pdf(&args, &outfile.to_string_lossy(), &pages)?;
+        pdf.save(&mut BufWriter::new(File::create(outfile).unwrap()))
+            .unwrap();
+
+        return Ok(());
+    }
+
     for file in &args.files {
-        let image = open_image(&file).map_err(|_| ())?;
-        let processed = process_image(&args, image);
-        let pdf = create_pdf(&file.to_string_lossy(), &processed);
+        let page = load_page(&args, file)?;
+        let pdf = create_pdf(&args, &file.to_string_lossy(), &page)?;
 
         let outfile = file.with_extension("pdf");
         pdf.save(&mut BufWriter::new(File::create(outfile).unwrap()))
@@ -141,3 +853,100 @@ fn main() -> std::result::Result<(), ()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_args() -> Args {
+        Args {
+            files: Vec::new(),
+            to_gray: false,
+            alpha: 100,
+            combine: None,
+            background: Background { r: 255, g: 255, b: 255 },
+            page_size: None,
+            margin: 0.0,
+            orientation: Orientation::Portrait,
+            max_dpi: None,
+            filter: ResizeFilter::Lanczos3,
+        }
+    }
+
+    #[test]
+    fn fit_to_page_without_page_size_is_a_no_op() {
+        let args = test_args();
+        let (width_pt, height_pt, scale) = fit_to_page(&args, 300.0, 600.0).unwrap();
+        assert_eq!((width_pt, height_pt, scale), (300.0, 600.0, 1.0));
+    }
+
+    #[test]
+    fn fit_to_page_scales_down_to_fit_the_smaller_dimension() {
+        let mut args = test_args();
+        args.page_size = Some(PaperSize::A4);
+        let (page_width_pt, page_height_pt) = PaperSize::A4.dimensions_pt();
+
+        // Much wider than an A4 page is tall, so the fit is constrained by width.
+        let (width_pt, height_pt, scale) = fit_to_page(&args, 10_000.0, 1.0).unwrap();
+        assert_eq!((width_pt, height_pt), (page_width_pt, page_height_pt));
+        assert!((scale - page_width_pt / 10_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fit_to_page_swaps_dimensions_for_landscape() {
+        let mut args = test_args();
+        args.page_size = Some(PaperSize::A4);
+        args.orientation = Orientation::Landscape;
+        let (portrait_width_pt, portrait_height_pt) = PaperSize::A4.dimensions_pt();
+
+        let (width_pt, height_pt, _) = fit_to_page(&args, 1.0, 1.0).unwrap();
+        assert_eq!((width_pt, height_pt), (portrait_height_pt, portrait_width_pt));
+    }
+
+    #[test]
+    fn fit_to_page_rejects_a_margin_that_leaves_no_printable_area() {
+        let mut args = test_args();
+        args.page_size = Some(PaperSize::A4);
+        let (page_width_pt, _) = PaperSize::A4.dimensions_pt();
+        args.margin = (page_width_pt / 2.0) / mm_to_pt(1.0);
+
+        assert_eq!(fit_to_page(&args, 100.0, 100.0), Err(()));
+    }
+
+    #[test]
+    fn target_physical_size_in_matches_natural_size_without_page_size() {
+        let args = test_args();
+        let (width_in, height_in) = target_physical_size_in(&args, 300, 600).unwrap();
+        assert!((width_in - 1.0).abs() < 1e-9);
+        assert!((height_in - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn downsample_to_max_dpi_is_a_no_op_without_max_dpi() {
+        let args = test_args();
+        let img = DynamicImage::ImageRgb8(ImageBuffer::new(300, 600));
+        let (output, dpi) = downsample_to_max_dpi(&args, img).unwrap();
+        assert_eq!((output.width(), output.height()), (300, 600));
+        assert_eq!(dpi, DPI);
+    }
+
+    #[test]
+    fn downsample_to_max_dpi_is_a_no_op_when_already_at_target() {
+        let mut args = test_args();
+        args.max_dpi = Some(DPI);
+        let img = DynamicImage::ImageRgb8(ImageBuffer::new(300, 600));
+        let (output, dpi) = downsample_to_max_dpi(&args, img).unwrap();
+        assert_eq!((output.width(), output.height()), (300, 600));
+        assert_eq!(dpi, DPI);
+    }
+
+    #[test]
+    fn downsample_to_max_dpi_resizes_down_to_the_target_dpi() {
+        let mut args = test_args();
+        args.max_dpi = Some(DPI / 2.0);
+        let img = DynamicImage::ImageRgb8(ImageBuffer::new(300, 600));
+        let (output, dpi) = downsample_to_max_dpi(&args, img).unwrap();
+        assert_eq!((output.width(), output.height()), (150, 300));
+        assert_eq!(dpi, DPI / 2.0);
+    }
+}